@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::*;
+use crate::error::*;
+
+/// Placeholder type for tokens that don't carry any custom claims.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NoCustomClaims {}
+
+fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// The claims of a JWT, combining the registered claims from RFC 7519 with
+/// any application-specific claims.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JWTClaims<CustomClaims> {
+    #[serde(rename = "iss", skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+    #[serde(rename = "sub", skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    #[serde(rename = "aud", skip_serializing_if = "Option::is_none")]
+    pub audiences: Option<std::collections::HashSet<String>>,
+    #[serde(rename = "iat", skip_serializing_if = "Option::is_none")]
+    pub issued_at: Option<u64>,
+    #[serde(rename = "exp", skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    #[serde(rename = "nbf", skip_serializing_if = "Option::is_none")]
+    pub invalid_before: Option<u64>,
+    #[serde(rename = "jti", skip_serializing_if = "Option::is_none")]
+    pub jwt_id: Option<String>,
+    #[serde(rename = "nonce", skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    #[serde(flatten)]
+    pub custom: CustomClaims,
+}
+
+impl<CustomClaims: Serialize + serde::de::DeserializeOwned> JWTClaims<CustomClaims> {
+    pub(crate) fn validate(&self, options: &VerificationOptions) -> Result<(), Error> {
+        let now = unix_timestamp();
+        let time_tolerance = options.time_tolerance.map(|d| d.as_secs()).unwrap_or(900);
+
+        if let Some(expires_at) = self.expires_at {
+            ensure!(
+                expires_at.saturating_add(time_tolerance) >= now,
+                JWTError::TokenHasExpired
+            );
+        }
+        if !options.accept_future {
+            if let Some(invalid_before) = self.invalid_before {
+                ensure!(
+                    invalid_before <= now.saturating_add(time_tolerance),
+                    JWTError::TokenNotValidYet
+                );
+            }
+        }
+        if let Some(max_validity) = options.max_validity {
+            if let (Some(issued_at), Some(expires_at)) = (self.issued_at, self.expires_at) {
+                ensure!(
+                    expires_at.saturating_sub(issued_at) <= max_validity.as_secs(),
+                    JWTError::TokenHasExpired
+                );
+            }
+        }
+        if let Some(reject_before) = options.reject_before {
+            if let Some(issued_at) = self.issued_at {
+                ensure!(issued_at >= reject_before.as_secs(), JWTError::TokenHasExpired);
+            }
+        }
+        if let Some(required_subject) = &options.required_subject {
+            match &self.subject {
+                Some(subject) => ensure!(
+                    subject == required_subject,
+                    JWTError::RequiredSubjectMismatch
+                ),
+                None => bail!(JWTError::RequiredSubjectMissing),
+            }
+        }
+        if let Some(required_nonce) = &options.required_nonce {
+            match &self.nonce {
+                Some(nonce) => ensure!(nonce == required_nonce, JWTError::RequiredNonceMismatch),
+                None => bail!(JWTError::RequiredNonceMissing),
+            }
+        }
+        if let Some(allowed_issuers) = &options.allowed_issuers {
+            match &self.issuer {
+                Some(issuer) => {
+                    ensure!(allowed_issuers.contains(issuer), JWTError::RequiredIssuerMismatch)
+                }
+                None => bail!(JWTError::RequiredIssuerMissing),
+            }
+        }
+        if let Some(allowed_audiences) = &options.allowed_audiences {
+            match &self.audiences {
+                Some(audiences) => ensure!(
+                    audiences.iter().any(|audience| allowed_audiences.contains(audience)),
+                    JWTError::RequiredAudienceMismatch
+                ),
+                None => bail!(JWTError::RequiredAudienceMissing),
+            }
+        }
+        Ok(())
+    }
+
+    /// Generate and record a random nonce, to be checked for with
+    /// `VerificationOptions::required_nonce` on verification.
+    pub fn create_nonce(&mut self) -> String {
+        use ct_codecs::{Base64UrlSafeNoPadding, Encoder};
+        let mut raw = [0u8; 24];
+        getrandom_fill(&mut raw);
+        let nonce = Base64UrlSafeNoPadding::encode_to_string(raw).unwrap_or_default();
+        self.nonce = Some(nonce.clone());
+        nonce
+    }
+}
+
+fn getrandom_fill(buf: &mut [u8]) {
+    use ring::rand::{SecureRandom, SystemRandom};
+    let _ = SystemRandom::new().fill(buf);
+}
+
+/// Builder for a fresh set of claims to be signed into a token.
+pub struct Claims;
+
+impl Claims {
+    /// Create claims valid for the given duration, starting now.
+    pub fn create(valid_for: Duration) -> JWTClaims<NoCustomClaims> {
+        Self::with_custom_claims(NoCustomClaims {}, valid_for)
+    }
+
+    /// Create claims carrying application-specific data, valid for the given
+    /// duration, starting now.
+    pub fn with_custom_claims<CustomClaims: Serialize + serde::de::DeserializeOwned>(
+        custom: CustomClaims,
+        valid_for: Duration,
+    ) -> JWTClaims<CustomClaims> {
+        let now = unix_timestamp();
+        JWTClaims {
+            issuer: None,
+            subject: None,
+            audiences: None,
+            issued_at: Some(now),
+            expires_at: Some(now + valid_for.as_secs()),
+            invalid_before: None,
+            jwt_id: None,
+            nonce: None,
+            custom,
+        }
+    }
+}
+
+impl<CustomClaims> JWTClaims<CustomClaims> {
+    pub fn with_issuer(mut self, issuer: impl ToString) -> Self {
+        self.issuer = Some(issuer.to_string());
+        self
+    }
+
+    pub fn with_subject(mut self, subject: impl ToString) -> Self {
+        self.subject = Some(subject.to_string());
+        self
+    }
+
+    pub fn with_audience(mut self, audience: impl ToString) -> Self {
+        let mut audiences = std::collections::HashSet::new();
+        audiences.insert(audience.to_string());
+        self.audiences = Some(audiences);
+        self
+    }
+
+    pub fn with_audiences<S: ToString>(
+        mut self,
+        audiences: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.audiences = Some(audiences.into_iter().map(|a| a.to_string()).collect());
+        self
+    }
+
+    pub fn with_jwt_id(mut self, jwt_id: impl ToString) -> Self {
+        self.jwt_id = Some(jwt_id.to_string());
+        self
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::HashSet as StdHashSet;
+use std::time::Duration as StdDuration;
+
+/// A thin wrapper around `std::time::Duration` with convenience constructors
+/// matching the units JWT claims are usually expressed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Duration(StdDuration);
+
+impl Duration {
+    pub fn from_secs(secs: u64) -> Self {
+        Duration(StdDuration::from_secs(secs))
+    }
+
+    pub fn from_mins(mins: u64) -> Self {
+        Self::from_secs(mins.saturating_mul(60))
+    }
+
+    pub fn from_hours(hours: u64) -> Self {
+        Self::from_mins(hours.saturating_mul(60))
+    }
+
+    pub fn from_days(days: u64) -> Self {
+        Self::from_hours(days.saturating_mul(24))
+    }
+
+    pub fn as_secs(&self) -> u64 {
+        self.0.as_secs()
+    }
+}
+
+impl From<StdDuration> for Duration {
+    fn from(duration: StdDuration) -> Self {
+        Duration(duration)
+    }
+}
+
+impl From<Duration> for StdDuration {
+    fn from(duration: Duration) -> Self {
+        duration.0
+    }
+}
+
+/// A set of strings, with a convenience constructor to build one from a slice
+/// of string-like values (used for issuers, audiences, and accepted critical
+/// headers).
+#[derive(Debug, Clone, Default)]
+pub struct HashSet(StdHashSet<String>);
+
+impl HashSet {
+    pub fn new() -> Self {
+        HashSet(StdHashSet::new())
+    }
+
+    pub fn from_strings<S: ToString>(items: &[S]) -> Self {
+        HashSet(items.iter().map(|item| item.to_string()).collect())
+    }
+
+    pub fn contains(&self, item: &str) -> bool {
+        self.0.contains(item)
+    }
+
+    pub fn insert(&mut self, item: impl ToString) -> bool {
+        self.0.insert(item.to_string())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &String> {
+        self.0.iter()
+    }
+}
+
+impl FromIterator<String> for HashSet {
+    fn from_iter<T: IntoIterator<Item = String>>(iter: T) -> Self {
+        HashSet(iter.into_iter().collect())
+    }
+}
+
+/// Options a caller can set to restrict what a token is allowed to assert,
+/// and to opt into optional protections.
+#[derive(Debug, Clone, Default)]
+pub struct VerificationOptions {
+    /// Reject tokens created before this amount of time has elapsed.
+    pub reject_before: Option<Duration>,
+    /// Accept tokens whose not-before (`nbf`) date is still in the future.
+    pub accept_future: bool,
+    /// The `sub` claim must match this value.
+    pub required_subject: Option<String>,
+    /// The `kid` header must match this value.
+    pub required_key_id: Option<String>,
+    /// The `jwk` header must match this value.
+    pub required_public_key: Option<String>,
+    /// The nonce claim must match this value.
+    pub required_nonce: Option<String>,
+    /// The `iss` claim must be one of these values.
+    pub allowed_issuers: Option<HashSet>,
+    /// The `aud` claim must contain at least one of these values.
+    pub allowed_audiences: Option<HashSet>,
+    /// Allow this much clock drift when checking expiration/not-before.
+    pub time_tolerance: Option<Duration>,
+    /// Reject tokens whose validity period exceeds this duration.
+    pub max_validity: Option<Duration>,
+    /// The set of `crit` header extensions the caller understands and accepts.
+    /// A token listing a `crit` extension outside this set is rejected.
+    pub accepted_critical_headers: Option<HashSet>,
+    /// Expected base64url-encoded SHA-256 thumbprint(s) of the certificate the
+    /// token's `x5t#S256` header must reference. Checked with a plain set
+    /// lookup rather than in constant time, since a thumbprint isn't a
+    /// secret; use `certificate_der` if that matters to you.
+    pub certificate_sha256_thumbprints: Option<HashSet>,
+    /// Expected base64url-encoded SHA-1 thumbprint(s) of the certificate the
+    /// token's `x5t` header must reference. Same non-constant-time lookup as
+    /// `certificate_sha256_thumbprints`.
+    pub certificate_sha1_thumbprints: Option<HashSet>,
+    /// DER-encoded certificate to pin: when set, its SHA-256 (and SHA-1, if
+    /// `x5t` is present) digest is compared against the token's thumbprint
+    /// headers instead of requiring the caller to precompute them.
+    pub certificate_der: Option<Vec<u8>>,
+    /// Reject tokens whose total length, in bytes, exceeds this value.
+    /// Defaults to `DEFAULT_MAX_TOKEN_LENGTH` when unset.
+    pub max_token_length: Option<usize>,
+}
@@ -0,0 +1,61 @@
+//! Minimal ASN.1 DER encoding helpers, just enough to build the
+//! `RSAPublicKey ::= SEQUENCE { modulus INTEGER, publicExponent INTEGER }`
+//! structure (RFC 8017, appendix A.1.1) from raw big-endian components.
+
+use crate::error::*;
+
+fn encode_length(len: usize, out: &mut Vec<u8>) {
+    if len < 0x80 {
+        out.push(len as u8);
+        return;
+    }
+    let mut len_bytes = Vec::new();
+    let mut remaining = len;
+    while remaining > 0 {
+        len_bytes.push((remaining & 0xff) as u8);
+        remaining >>= 8;
+    }
+    len_bytes.reverse();
+    out.push(0x80 | len_bytes.len() as u8);
+    out.extend_from_slice(&len_bytes);
+}
+
+/// DER-encode an unsigned big-endian integer, stripping any leading zero
+/// bytes the caller may have included and re-adding exactly one if needed to
+/// keep the high bit from being interpreted as a sign.
+pub fn encode_unsigned_integer(raw: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut value = raw;
+    while value.len() > 1 && value[0] == 0 {
+        value = &value[1..];
+    }
+    ensure!(!value.is_empty(), JWTError::InvalidJWKKeyMaterial);
+    let mut out = vec![0x02u8];
+    if value[0] & 0x80 != 0 {
+        let mut content = vec![0u8];
+        content.extend_from_slice(value);
+        encode_length(content.len(), &mut out);
+        out.extend_from_slice(&content);
+    } else {
+        encode_length(value.len(), &mut out);
+        out.extend_from_slice(value);
+    }
+    Ok(out)
+}
+
+fn encode_sequence(members: &[Vec<u8>]) -> Vec<u8> {
+    let content: Vec<u8> = members.iter().flatten().copied().collect();
+    let mut out = vec![0x30u8];
+    encode_length(content.len(), &mut out);
+    out.extend_from_slice(&content);
+    out
+}
+
+/// Build a DER-encoded `RSAPublicKey` from the raw, big-endian modulus and
+/// public exponent.
+pub fn rsa_public_key(modulus: &[u8], exponent: &[u8]) -> Result<Vec<u8>, Error> {
+    ensure!(!modulus.is_empty(), JWTError::InvalidJWKKeyMaterial);
+    ensure!(!exponent.is_empty(), JWTError::InvalidJWKKeyMaterial);
+    let n = encode_unsigned_integer(modulus)?;
+    let e = encode_unsigned_integer(exponent)?;
+    Ok(encode_sequence(&[n, e]))
+}
@@ -0,0 +1,68 @@
+use p256::ecdsa::signature::Verifier;
+use p256::ecdsa::{Signature, VerifyingKey};
+use p256::EncodedPoint;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::claims::*;
+use crate::common::*;
+use crate::error::*;
+use crate::token::*;
+
+/// The size, in bytes, of a P-256 field element.
+const P256_COORDINATE_LEN: usize = 32;
+
+/// An ECDSA P-256 public key, used to verify tokens produced by the matching
+/// private key.
+#[derive(Debug, Clone)]
+pub struct ES256PublicKey {
+    verifying_key: VerifyingKey,
+    key_id: Option<String>,
+}
+
+impl ES256PublicKey {
+    /// Import a public key from an uncompressed SEC1 point.
+    pub fn from_bytes(raw: &[u8]) -> Result<Self, Error> {
+        let verifying_key =
+            VerifyingKey::from_sec1_bytes(raw).map_err(|_| JWTError::InvalidJWKKeyMaterial)?;
+        Ok(ES256PublicKey {
+            verifying_key,
+            key_id: None,
+        })
+    }
+
+    /// Import a public key from the raw, base64url-decoded `x` and `y`
+    /// coordinates of a JWK. Both coordinates must be exactly
+    /// `P256_COORDINATE_LEN` bytes, matching the P-256 curve.
+    pub fn from_coordinates(x: &[u8], y: &[u8]) -> Result<Self, Error> {
+        ensure!(
+            x.len() == P256_COORDINATE_LEN && y.len() == P256_COORDINATE_LEN,
+            JWTError::InvalidJWKKeyMaterial
+        );
+        let point = EncodedPoint::from_affine_coordinates(x.into(), y.into(), false);
+        Self::from_bytes(point.as_bytes())
+    }
+
+    pub fn key_id(&self) -> &Option<String> {
+        &self.key_id
+    }
+
+    pub fn with_key_id(mut self, key_id: &str) -> Self {
+        self.key_id = Some(key_id.to_string());
+        self
+    }
+
+    pub fn verify_token<CustomClaims: Serialize + DeserializeOwned>(
+        &self,
+        token: &str,
+        options: Option<VerificationOptions>,
+    ) -> Result<JWTClaims<CustomClaims>, Error> {
+        Token::verify("ES256", token, options, |authenticated, signature| {
+            let signature =
+                Signature::from_slice(signature).map_err(|_| JWTError::InvalidSignature)?;
+            self.verifying_key
+                .verify(authenticated.as_bytes(), &signature)
+                .map_err(|_| JWTError::InvalidAuthenticationTag)?;
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,70 @@
+pub use anyhow::{bail, ensure, Error};
+use thiserror::Error as ThisError;
+
+#[derive(Debug, ThisError)]
+pub enum JWTError {
+    #[error("Authentication failed")]
+    InvalidAuthenticationTag,
+    #[error("Signature too short")]
+    InvalidSignature,
+    #[error("Token is not a compact JWT")]
+    CompactEncodingError,
+    #[error("Header too large")]
+    HeaderTooLarge,
+    #[error("Not a JWT")]
+    NotJWT,
+    #[error("Algorithm mismatch")]
+    AlgorithmMismatch,
+    #[error("Key identifier mismatch")]
+    KeyIdentifierMismatch,
+    #[error("Key identifier required, but not present in the token header")]
+    MissingJWTKeyIdentifier,
+    #[error("Public key mismatch")]
+    PublicKeyMismatch,
+    #[error("Public key required, but not present in the token header")]
+    MissingJWTPublicKey,
+    #[error("Invalid certificate thumbprint")]
+    InvalidCertThumprint,
+    #[error("Token has expired")]
+    TokenHasExpired,
+    #[error("Token not yet valid")]
+    TokenNotValidYet,
+    #[error("Required subject is missing")]
+    RequiredSubjectMissing,
+    #[error("Subject mismatch")]
+    RequiredSubjectMismatch,
+    #[error("Required nonce is missing")]
+    RequiredNonceMissing,
+    #[error("Nonce mismatch")]
+    RequiredNonceMismatch,
+    #[error("Issuer mismatch")]
+    RequiredIssuerMismatch,
+    #[error("Issuer is missing")]
+    RequiredIssuerMissing,
+    #[error("Audience mismatch")]
+    RequiredAudienceMismatch,
+    #[error("Audience is missing")]
+    RequiredAudienceMissing,
+    #[error("The `crit` header is empty")]
+    EmptyCriticalHeader,
+    #[error("The `crit` header lists a registered header name")]
+    RegisteredCriticalHeader,
+    #[error("The `crit` header references a header that is not present")]
+    MissingCriticalHeaderValue,
+    #[error("The `crit` header lists an extension that is not accepted by the verifier")]
+    UnsupportedCriticalHeader,
+    #[error("The certificate thumbprint doesn't match the token's `x5t`/`x5t#S256` header")]
+    CertificateThumbprintMismatch,
+    #[error("The token doesn't include the certificate thumbprint the verifier requires")]
+    MissingCertificateThumbprint,
+    #[error("Token is larger than the maximum accepted length")]
+    TokenTooLong,
+    #[error("JWK set doesn't contain a key matching the requested key identifier")]
+    NoMatchingJWKForKeyId,
+    #[error("JWK algorithm doesn't match the algorithm the verifier expects")]
+    JWKAlgorithmMismatch,
+    #[error("Unsupported JWK key type")]
+    UnsupportedJWKKeyType,
+    #[error("Invalid JWK key material")]
+    InvalidJWKKeyMaterial,
+}
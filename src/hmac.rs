@@ -0,0 +1,73 @@
+use hmac::{Hmac, Mac};
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::Sha256;
+
+use crate::claims::*;
+use crate::common::*;
+use crate::error::*;
+use crate::token::*;
+
+/// An HMAC-SHA256 key, used both to authenticate and to verify tokens.
+#[derive(Debug, Clone)]
+pub struct HS256Key {
+    key: Vec<u8>,
+    key_id: Option<String>,
+}
+
+impl HS256Key {
+    /// Create a new key from raw bytes.
+    pub fn from_bytes(raw: &[u8]) -> Self {
+        HS256Key {
+            key: raw.to_vec(),
+            key_id: None,
+        }
+    }
+
+    /// Generate a new random key.
+    pub fn generate() -> Self {
+        use ring::rand::{SecureRandom, SystemRandom};
+        let mut raw = [0u8; 32];
+        let _ = SystemRandom::new().fill(&mut raw);
+        Self::from_bytes(&raw)
+    }
+
+    pub fn key_id(&self) -> &Option<String> {
+        &self.key_id
+    }
+
+    pub fn with_key_id(mut self, key_id: &str) -> Self {
+        self.key_id = Some(key_id.to_string());
+        self
+    }
+
+    fn authentication_tag(&self, authenticated: &str) -> Result<Vec<u8>, Error> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.key).map_err(|_| JWTError::InvalidSignature)?;
+        mac.update(authenticated.as_bytes());
+        Ok(mac.finalize().into_bytes().to_vec())
+    }
+
+    pub fn authenticate<CustomClaims: Serialize + DeserializeOwned>(
+        &self,
+        claims: JWTClaims<CustomClaims>,
+    ) -> Result<String, Error> {
+        let jwt_header = NewTokenMetadata::new("HS256".to_string(), self.key_id.clone()).jwt_header;
+        Token::build(&jwt_header, claims, |authenticated| {
+            self.authentication_tag(authenticated)
+        })
+    }
+
+    pub fn verify_token<CustomClaims: Serialize + DeserializeOwned>(
+        &self,
+        token: &str,
+        options: Option<VerificationOptions>,
+    ) -> Result<JWTClaims<CustomClaims>, Error> {
+        Token::verify("HS256", token, options, |authenticated, tag| {
+            let expected = self.authentication_tag(authenticated)?;
+            ensure!(
+                ring::constant_time::verify_slices_are_equal(tag, &expected).is_ok(),
+                JWTError::InvalidAuthenticationTag
+            );
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,311 @@
+use ct_codecs::{Base64UrlSafeNoPadding, Decoder, Encoder};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::common::*;
+use crate::ecdsa::*;
+use crate::error::*;
+use crate::claims::*;
+use crate::hmac::*;
+use crate::rsa::*;
+use crate::token::*;
+
+/// A single entry of a JSON Web Key Set (RFC 7517).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JWK {
+    /// The key type: `RSA`, `EC`, or `oct`.
+    pub kty: String,
+    /// The key identifier, matched against a token's `kid` header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    /// The intended use of the key, e.g. `sig`.
+    #[serde(rename = "use", skip_serializing_if = "Option::is_none")]
+    pub use_: Option<String>,
+    /// The algorithm this key is meant to be used with, e.g. `RS256`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+    /// RSA modulus, base64url-encoded, big-endian.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    /// RSA public exponent, base64url-encoded, big-endian.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+    /// The EC curve, e.g. `P-256`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+    /// EC `x` coordinate, base64url-encoded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+    /// EC `y` coordinate, base64url-encoded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+    /// Symmetric key material, base64url-encoded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub k: Option<String>,
+}
+
+/// A JSON Web Key Set, as published at a `jwks_uri` endpoint (RFC 7517).
+#[derive(Debug, Clone, Deserialize)]
+pub struct JWKSet {
+    pub keys: Vec<JWK>,
+}
+
+impl JWKSet {
+    /// Parse a JWK set from its standard `{"keys": [...]}` JSON representation.
+    pub fn parse(json: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Find the JWK whose `kid` matches the given key identifier.
+    pub fn find(&self, kid: &str) -> Option<&JWK> {
+        self.keys
+            .iter()
+            .find(|jwk| jwk.kid.as_deref() == Some(kid))
+    }
+
+    /// Decode a token's metadata, locate the matching JWK by `kid`, build the
+    /// corresponding key, and verify the token against it.
+    pub fn verify_token<CustomClaims: Serialize + DeserializeOwned>(
+        &self,
+        token: &str,
+        options: Option<VerificationOptions>,
+    ) -> Result<JWTClaims<CustomClaims>, Error> {
+        let metadata = Token::decode_metadata(token)?;
+        let key_id = metadata.key_id().ok_or(JWTError::MissingJWTKeyIdentifier)?;
+        let jwk = self.find(key_id).ok_or(JWTError::NoMatchingJWKForKeyId)?;
+        jwk.verify_token(metadata.algorithm(), token, options)
+    }
+}
+
+impl JWK {
+    fn decode_component(component: &Option<String>) -> Result<Vec<u8>, Error> {
+        let encoded = component.as_deref().ok_or(JWTError::InvalidJWKKeyMaterial)?;
+        Ok(Base64UrlSafeNoPadding::decode_to_vec(encoded, None)?)
+    }
+
+    fn check_algorithm(&self, expected_algorithm: &str) -> Result<(), Error> {
+        if let Some(alg) = &self.alg {
+            ensure!(alg == expected_algorithm, JWTError::JWKAlgorithmMismatch);
+        }
+        Ok(())
+    }
+
+    /// Verify a token against this single JWK, checking that the key's
+    /// declared `alg` (if any) matches the algorithm the token claims to use.
+    pub fn verify_token<CustomClaims: Serialize + DeserializeOwned>(
+        &self,
+        expected_algorithm: &str,
+        token: &str,
+        options: Option<VerificationOptions>,
+    ) -> Result<JWTClaims<CustomClaims>, Error> {
+        self.check_algorithm(expected_algorithm)?;
+        match (self.kty.as_str(), expected_algorithm) {
+            ("RSA", "RS256") => {
+                let n = Self::decode_component(&self.n)?;
+                let e = Self::decode_component(&self.e)?;
+                RS256PublicKey::from_components(&n, &e)?.verify_token(token, options)
+            }
+            ("RSA", "RS384") => {
+                let n = Self::decode_component(&self.n)?;
+                let e = Self::decode_component(&self.e)?;
+                RS384PublicKey::from_components(&n, &e)?.verify_token(token, options)
+            }
+            ("RSA", "RS512") => {
+                let n = Self::decode_component(&self.n)?;
+                let e = Self::decode_component(&self.e)?;
+                RS512PublicKey::from_components(&n, &e)?.verify_token(token, options)
+            }
+            ("EC", "ES256") => {
+                let x = Self::decode_component(&self.x)?;
+                let y = Self::decode_component(&self.y)?;
+                ES256PublicKey::from_coordinates(&x, &y)?.verify_token(token, options)
+            }
+            ("oct", "HS256") => {
+                let k = Self::decode_component(&self.k)?;
+                HS256Key::from_bytes(&k).verify_token(token, options)
+            }
+            _ => bail!(JWTError::UnsupportedJWKKeyType),
+        }
+    }
+}
+
+#[test]
+fn jwkset_selects_rsa_key_by_kid() {
+    use crate::prelude::*;
+    use rsa::pkcs1v15::SigningKey;
+    use rsa::signature::{SignatureEncoding, Signer};
+    use rsa::{RsaPrivateKey, RsaPublicKey};
+    use sha2::Sha256;
+
+    let private_key = RsaPrivateKey::new(&mut rand::thread_rng(), 2048).unwrap();
+    let public_key = RsaPublicKey::from(&private_key);
+    let signing_key = SigningKey::<Sha256>::new(private_key);
+
+    let n = Base64UrlSafeNoPadding::encode_to_string(public_key.n().to_bytes_be()).unwrap();
+    let e = Base64UrlSafeNoPadding::encode_to_string(public_key.e().to_bytes_be()).unwrap();
+
+    let jwt_header =
+        NewTokenMetadata::new("RS256".to_string(), Some("rsa-key".to_string())).jwt_header;
+    let claims = Claims::create(Duration::from_mins(10));
+    let token = Token::build(&jwt_header, claims, |authenticated| {
+        Ok(signing_key.sign(authenticated.as_bytes()).to_vec())
+    })
+    .unwrap();
+
+    let jwk_set = JWKSet {
+        keys: vec![JWK {
+            kty: "RSA".to_string(),
+            kid: Some("rsa-key".to_string()),
+            use_: Some("sig".to_string()),
+            alg: Some("RS256".to_string()),
+            n: Some(n),
+            e: Some(e),
+            crv: None,
+            x: None,
+            y: None,
+            k: None,
+        }],
+    };
+
+    jwk_set
+        .verify_token::<NoCustomClaims>(&token, None)
+        .unwrap();
+}
+
+#[test]
+fn jwkset_selects_ec_key_by_kid() {
+    use crate::prelude::*;
+    use p256::ecdsa::signature::{SignatureEncoding, Signer};
+    use p256::ecdsa::{Signature, SigningKey};
+
+    let signing_key = SigningKey::random(&mut rand::thread_rng());
+    let verifying_key = signing_key.verifying_key();
+    let encoded_point = verifying_key.to_encoded_point(false);
+    let x = Base64UrlSafeNoPadding::encode_to_string(encoded_point.x().unwrap()).unwrap();
+    let y = Base64UrlSafeNoPadding::encode_to_string(encoded_point.y().unwrap()).unwrap();
+
+    let jwt_header =
+        NewTokenMetadata::new("ES256".to_string(), Some("ec-key".to_string())).jwt_header;
+    let claims = Claims::create(Duration::from_mins(10));
+    let token = Token::build(&jwt_header, claims, |authenticated| {
+        let signature: Signature = signing_key.sign(authenticated.as_bytes());
+        Ok(signature.to_vec())
+    })
+    .unwrap();
+
+    let jwk_set = JWKSet {
+        keys: vec![JWK {
+            kty: "EC".to_string(),
+            kid: Some("ec-key".to_string()),
+            use_: Some("sig".to_string()),
+            alg: Some("ES256".to_string()),
+            n: None,
+            e: None,
+            crv: Some("P-256".to_string()),
+            x: Some(x),
+            y: Some(y),
+            k: None,
+        }],
+    };
+
+    jwk_set
+        .verify_token::<NoCustomClaims>(&token, None)
+        .unwrap();
+}
+
+#[test]
+fn jwkset_selects_oct_key_by_kid() {
+    use crate::prelude::*;
+
+    let key_bytes = b"super-secret-hmac-key-bytes-for-testing";
+    let k = Base64UrlSafeNoPadding::encode_to_string(key_bytes).unwrap();
+    let key = HS256Key::from_bytes(key_bytes).with_key_id("oct-key");
+    let claims = Claims::create(Duration::from_mins(10));
+    let token = key.authenticate(claims).unwrap();
+
+    let jwk_set = JWKSet {
+        keys: vec![JWK {
+            kty: "oct".to_string(),
+            kid: Some("oct-key".to_string()),
+            use_: Some("sig".to_string()),
+            alg: Some("HS256".to_string()),
+            n: None,
+            e: None,
+            crv: None,
+            x: None,
+            y: None,
+            k: Some(k),
+        }],
+    };
+
+    jwk_set
+        .verify_token::<NoCustomClaims>(&token, None)
+        .unwrap();
+}
+
+#[test]
+fn jwkset_no_matching_kid_errors() {
+    use crate::prelude::*;
+
+    let key = HS256Key::generate().with_key_id("unknown-key");
+    let claims = Claims::create(Duration::from_mins(10));
+    let token = key.authenticate(claims).unwrap();
+
+    let jwk_set = JWKSet { keys: vec![] };
+    let err = jwk_set
+        .verify_token::<NoCustomClaims>(&token, None)
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<JWTError>(),
+        Some(JWTError::NoMatchingJWKForKeyId)
+    ));
+}
+
+#[test]
+fn jwkset_missing_kid_header_errors() {
+    use crate::prelude::*;
+
+    let key = HS256Key::generate();
+    let claims = Claims::create(Duration::from_mins(10));
+    let token = key.authenticate(claims).unwrap();
+
+    let jwk_set = JWKSet { keys: vec![] };
+    let err = jwk_set
+        .verify_token::<NoCustomClaims>(&token, None)
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<JWTError>(),
+        Some(JWTError::MissingJWTKeyIdentifier)
+    ));
+}
+
+#[test]
+fn jwk_alg_mismatch_errors() {
+    use crate::prelude::*;
+
+    let key = HS256Key::generate().with_key_id("mismatched-key");
+    let claims = Claims::create(Duration::from_mins(10));
+    let token = key.authenticate(claims).unwrap();
+
+    let jwk_set = JWKSet {
+        keys: vec![JWK {
+            kty: "oct".to_string(),
+            kid: Some("mismatched-key".to_string()),
+            use_: Some("sig".to_string()),
+            alg: Some("RS256".to_string()),
+            n: None,
+            e: None,
+            crv: None,
+            x: None,
+            y: None,
+            k: None,
+        }],
+    };
+
+    let err = jwk_set
+        .verify_token::<NoCustomClaims>(&token, None)
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<JWTError>(),
+        Some(JWTError::JWKAlgorithmMismatch)
+    ));
+}
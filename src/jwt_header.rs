@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JWTHeader {
+    #[serde(rename = "alg")]
+    pub algorithm: String,
+    #[serde(rename = "cty", skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(rename = "kid", skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+    #[serde(rename = "typ", skip_serializing_if = "Option::is_none")]
+    pub signature_type: Option<String>,
+    #[serde(rename = "crit", skip_serializing_if = "Option::is_none")]
+    pub critical: Option<Vec<String>>,
+    #[serde(rename = "x5c", skip_serializing_if = "Option::is_none")]
+    pub certificate_chain: Option<Vec<String>>,
+    #[serde(rename = "jku", skip_serializing_if = "Option::is_none")]
+    pub key_set_url: Option<String>,
+    #[serde(rename = "jwk", skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    #[serde(rename = "x5u", skip_serializing_if = "Option::is_none")]
+    pub certificate_url: Option<String>,
+    #[serde(rename = "x5t", skip_serializing_if = "Option::is_none")]
+    pub certificate_sha1_thumbprint: Option<String>,
+    #[serde(rename = "x5t#S256", skip_serializing_if = "Option::is_none")]
+    pub certificate_sha256_thumbprint: Option<String>,
+}
+
+/// Header names defined by RFC 7515 that a `crit` entry must never list,
+/// since they are already mandatory to understand.
+pub const REGISTERED_HEADER_NAMES: &[&str] = &[
+    "alg", "jku", "jwk", "kid", "x5u", "x5c", "x5t", "x5t#S256", "typ", "cty", "crit",
+];
@@ -0,0 +1,14 @@
+//! A small, opinionated JWT (JSON Web Token) library.
+
+mod claims;
+mod common;
+mod der;
+mod ecdsa;
+mod error;
+mod hmac;
+mod jwk;
+mod jwt_header;
+mod rsa;
+mod token;
+
+pub mod prelude;
@@ -0,0 +1,9 @@
+pub use crate::claims::*;
+pub use crate::common::*;
+pub use crate::ecdsa::*;
+pub use crate::error::*;
+pub use crate::hmac::*;
+pub use crate::jwk::*;
+pub use crate::jwt_header::*;
+pub use crate::rsa::*;
+pub use crate::token::*;
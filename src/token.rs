@@ -1,13 +1,19 @@
 use ct_codecs::{Base64UrlSafeNoPadding, Decoder, Encoder, Hex};
+use ring::{constant_time, digest};
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::claims::*;
 use crate::common::*;
 use crate::error::*;
+use crate::jwk::JWK;
 use crate::jwt_header::*;
 
 pub const MAX_HEADER_LENGTH: usize = 8192;
 
+/// The default upper bound on a token's total length, used when
+/// `VerificationOptions::max_token_length` isn't set.
+pub const DEFAULT_MAX_TOKEN_LENGTH: usize = 1024 * 1024;
+
 /// Utilities to get information about a JWT token
 pub struct Token;
 
@@ -122,6 +128,10 @@ impl Token {
         AuthenticationOrSignatureFn: FnOnce(&str, &[u8]) -> Result<(), Error>,
     {
         let options = options.unwrap_or_default();
+        ensure!(
+            token.len() <= options.max_token_length.unwrap_or(DEFAULT_MAX_TOKEN_LENGTH),
+            JWTError::TokenTooLong
+        );
         let mut parts = token.split('.');
         let jwt_header_b64 = parts.next().ok_or(JWTError::CompactEncodingError)?;
         ensure!(
@@ -131,9 +141,8 @@ impl Token {
         let claims_b64 = parts.next().ok_or(JWTError::CompactEncodingError)?;
         let authentication_tag_b64 = parts.next().ok_or(JWTError::CompactEncodingError)?;
         ensure!(parts.next().is_none(), JWTError::CompactEncodingError);
-        let jwt_header: JWTHeader = serde_json::from_slice(
-            &Base64UrlSafeNoPadding::decode_to_vec(jwt_header_b64, None)?,
-        )?;
+        let jwt_header_json = Base64UrlSafeNoPadding::decode_to_vec(jwt_header_b64, None)?;
+        let jwt_header: JWTHeader = serde_json::from_slice(&jwt_header_json)?;
         if let Some(signature_type) = &jwt_header.signature_type {
             ensure!(signature_type == "JWT", JWTError::NotJWT);
         }
@@ -148,6 +157,44 @@ impl Token {
                 bail!(JWTError::MissingJWTKeyIdentifier)
             }
         }
+        if let Some(required_public_key) = &options.required_public_key {
+            if let Some(public_key) = &jwt_header.public_key {
+                ensure!(
+                    public_key == required_public_key,
+                    JWTError::PublicKeyMismatch
+                );
+            } else {
+                bail!(JWTError::MissingJWTPublicKey)
+            }
+        }
+        if let Some(critical) = &jwt_header.critical {
+            ensure!(!critical.is_empty(), JWTError::EmptyCriticalHeader);
+            let header_map: serde_json::Map<String, serde_json::Value> =
+                serde_json::from_slice(&jwt_header_json)?;
+            for name in critical {
+                ensure!(
+                    !REGISTERED_HEADER_NAMES.contains(&name.as_str()),
+                    JWTError::RegisteredCriticalHeader
+                );
+                ensure!(
+                    header_map.contains_key(name),
+                    JWTError::MissingCriticalHeaderValue
+                );
+                ensure!(
+                    options
+                        .accepted_critical_headers
+                        .as_ref()
+                        .map_or(false, |accepted| accepted.contains(name)),
+                    JWTError::UnsupportedCriticalHeader
+                );
+            }
+        }
+        if options.certificate_der.is_some()
+            || options.certificate_sha256_thumbprints.is_some()
+            || options.certificate_sha1_thumbprints.is_some()
+        {
+            Self::verify_certificate_thumbprint(&jwt_header, &options)?;
+        }
         let authentication_tag =
             Base64UrlSafeNoPadding::decode_to_vec(&authentication_tag_b64, None)?;
         let authenticated = &token[..jwt_header_b64.len() + 1 + claims_b64.len()];
@@ -158,6 +205,81 @@ impl Token {
         Ok(claims)
     }
 
+    /// Check the token's `x5t#S256`/`x5t` headers against the certificate the
+    /// caller trusts, either given directly as a DER-encoded certificate or
+    /// as precomputed base64url thumbprints.
+    ///
+    /// The constant-time comparison only applies to the `certificate_der`
+    /// path, where we compute the expected digest ourselves. The precomputed
+    /// `certificate_sha256_thumbprints`/`certificate_sha1_thumbprints` paths
+    /// use a plain set lookup instead: a thumbprint is not a secret (it's
+    /// derived from a public certificate and travels unprotected in the
+    /// header), so timing variation in that comparison doesn't expose
+    /// anything a network observer couldn't already read from the token.
+    fn verify_certificate_thumbprint(
+        jwt_header: &JWTHeader,
+        options: &VerificationOptions,
+    ) -> Result<(), Error> {
+        if let Some(certificate_der) = &options.certificate_der {
+            let expected_sha256 = Base64UrlSafeNoPadding::encode_to_string(
+                digest::digest(&digest::SHA256, certificate_der),
+            )?;
+            let thumbprint = jwt_header
+                .certificate_sha256_thumbprint
+                .as_deref()
+                .ok_or(JWTError::MissingCertificateThumbprint)?;
+            ensure!(
+                constant_time::verify_slices_are_equal(
+                    thumbprint.as_bytes(),
+                    expected_sha256.as_bytes()
+                )
+                .is_ok(),
+                JWTError::CertificateThumbprintMismatch
+            );
+            // The token itself tells us whether it carries a legacy `x5t`:
+            // check it whenever present, rather than making its presence in
+            // `options` a proxy for "the caller cares about SHA-1".
+            if let Some(thumbprint) = &jwt_header.certificate_sha1_thumbprint {
+                let expected_sha1 = Base64UrlSafeNoPadding::encode_to_string(digest::digest(
+                    &digest::SHA1_FOR_LEGACY_USE_ONLY,
+                    certificate_der,
+                ))?;
+                ensure!(
+                    constant_time::verify_slices_are_equal(
+                        thumbprint.as_bytes(),
+                        expected_sha1.as_bytes()
+                    )
+                    .is_ok(),
+                    JWTError::CertificateThumbprintMismatch
+                );
+            }
+            return Ok(());
+        }
+        // Thumbprints aren't secret, so a non-constant-time set lookup here
+        // doesn't leak anything an attacker couldn't already see in the token.
+        if let Some(expected_sha256_thumbprints) = &options.certificate_sha256_thumbprints {
+            let thumbprint = jwt_header
+                .certificate_sha256_thumbprint
+                .as_deref()
+                .ok_or(JWTError::MissingCertificateThumbprint)?;
+            ensure!(
+                expected_sha256_thumbprints.contains(thumbprint),
+                JWTError::CertificateThumbprintMismatch
+            );
+        }
+        if let Some(expected_sha1_thumbprints) = &options.certificate_sha1_thumbprints {
+            let thumbprint = jwt_header
+                .certificate_sha1_thumbprint
+                .as_deref()
+                .ok_or(JWTError::MissingCertificateThumbprint)?;
+            ensure!(
+                expected_sha1_thumbprints.contains(thumbprint),
+                JWTError::CertificateThumbprintMismatch
+            );
+        }
+        Ok(())
+    }
+
     /// Decode token information that can be usedful prior to signature/tag verification
     pub fn decode_metadata(token: &str) -> Result<TokenMetadata, Error> {
         let mut parts = token.split('.');
@@ -199,6 +321,13 @@ impl NewTokenMetadata {
         self
     }
 
+    /// Embed a public key's JWK representation in the header, so a verifier
+    /// can reconstruct the key without a PEM round-trip.
+    pub fn with_public_key_jwk(mut self, jwk: &JWK) -> Result<Self, Error> {
+        self.jwt_header.public_key = Some(serde_json::to_string(jwk)?);
+        Ok(self)
+    }
+
     pub fn with_certificate_url(mut self, certificate_url: impl ToString) -> Self {
         self.jwt_header.certificate_url = Some(certificate_url.to_string());
         self
@@ -239,14 +368,14 @@ impl NewTokenMetadata {
                 JWTError::InvalidCertThumprint
             );
             let thumbprint = Base64UrlSafeNoPadding::encode_to_string(&bin)?;
-            self.jwt_header.certificate_sha1_thumbprint = Some(thumbprint);
+            self.jwt_header.certificate_sha256_thumbprint = Some(thumbprint);
             return Ok(self);
         }
         ensure!(
             Base64UrlSafeNoPadding::decode(&mut bin, &thumbprint, None)?.len() == bin.len(),
             JWTError::InvalidCertThumprint
         );
-        self.jwt_header.certificate_sha1_thumbprint = Some(thumbprint);
+        self.jwt_header.certificate_sha256_thumbprint = Some(thumbprint);
         Ok(self)
     }
 }
@@ -320,3 +449,230 @@ fn explicitly_empty_audiences() {
     let decoded = key.verify_token::<NoCustomClaims>(&token, None).unwrap();
     assert!(decoded.audiences.is_none());
 }
+
+#[test]
+fn certificate_sha256_thumbprint_mint_then_pin() {
+    use crate::prelude::*;
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256 as HmacSha256;
+
+    let certificate_der = b"fake-certificate-for-testing".to_vec();
+    let expected_thumbprint = Base64UrlSafeNoPadding::encode_to_string(ring::digest::digest(
+        &ring::digest::SHA256,
+        &certificate_der,
+    ))
+    .unwrap();
+
+    let jwt_header = NewTokenMetadata::new("HS256".to_string(), None)
+        .with_certificate_sha256_thumbprint(&expected_thumbprint)
+        .unwrap()
+        .jwt_header;
+    // The thumbprint must land in the `x5t#S256` header, not `x5t`.
+    assert_eq!(
+        jwt_header.certificate_sha256_thumbprint.as_deref(),
+        Some(expected_thumbprint.as_str())
+    );
+
+    let key_bytes = b"test-hmac-key-for-round-trip";
+    let claims = Claims::create(Duration::from_mins(10));
+    let token = Token::build(&jwt_header, claims, |authenticated| {
+        let mut mac = Hmac::<HmacSha256>::new_from_slice(key_bytes).unwrap();
+        mac.update(authenticated.as_bytes());
+        Ok(mac.finalize().into_bytes().to_vec())
+    })
+    .unwrap();
+
+    let options = VerificationOptions {
+        certificate_der: Some(certificate_der),
+        ..Default::default()
+    };
+    Token::verify::<_, NoCustomClaims>("HS256", &token, Some(options), |authenticated, tag| {
+        let mut mac = Hmac::<HmacSha256>::new_from_slice(key_bytes).unwrap();
+        mac.update(authenticated.as_bytes());
+        let expected = mac.finalize().into_bytes().to_vec();
+        ensure!(
+            ring::constant_time::verify_slices_are_equal(tag, &expected).is_ok(),
+            JWTError::InvalidAuthenticationTag
+        );
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Build a compact token from a hand-written header JSON string, so tests
+/// can embed extension headers `JWTHeader` doesn't model as struct fields.
+#[cfg(test)]
+fn build_token_with_raw_header(header_json: &str, key_bytes: &[u8]) -> String {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256 as HmacSha256;
+
+    let claims_json =
+        serde_json::to_string(&Claims::create(Duration::from_mins(10))).unwrap();
+    let header_b64 = Base64UrlSafeNoPadding::encode_to_string(header_json).unwrap();
+    let claims_b64 = Base64UrlSafeNoPadding::encode_to_string(claims_json).unwrap();
+    let authenticated = format!("{}.{}", header_b64, claims_b64);
+    let mut mac = Hmac::<HmacSha256>::new_from_slice(key_bytes).unwrap();
+    mac.update(authenticated.as_bytes());
+    let tag = mac.finalize().into_bytes().to_vec();
+    format!(
+        "{}.{}",
+        authenticated,
+        Base64UrlSafeNoPadding::encode_to_string(tag).unwrap()
+    )
+}
+
+#[cfg(test)]
+fn verify_with_raw_key<CustomClaims: Serialize + DeserializeOwned>(
+    token: &str,
+    key_bytes: &[u8],
+    options: Option<VerificationOptions>,
+) -> Result<JWTClaims<CustomClaims>, Error> {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256 as HmacSha256;
+
+    Token::verify("HS256", token, options, |authenticated, tag| {
+        let mut mac = Hmac::<HmacSha256>::new_from_slice(key_bytes).unwrap();
+        mac.update(authenticated.as_bytes());
+        let expected = mac.finalize().into_bytes().to_vec();
+        ensure!(
+            ring::constant_time::verify_slices_are_equal(tag, &expected).is_ok(),
+            JWTError::InvalidAuthenticationTag
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn crit_empty_header_is_rejected() {
+    let key_bytes = b"crit-test-key";
+    let token = build_token_with_raw_header(r#"{"alg":"HS256","crit":[]}"#, key_bytes);
+    let err = verify_with_raw_key::<NoCustomClaims>(&token, key_bytes, None).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<JWTError>(),
+        Some(JWTError::EmptyCriticalHeader)
+    ));
+}
+
+#[test]
+fn crit_registered_header_name_is_rejected() {
+    let key_bytes = b"crit-test-key";
+    let token = build_token_with_raw_header(
+        r#"{"alg":"HS256","crit":["kid"],"kid":"some-key"}"#,
+        key_bytes,
+    );
+    let err = verify_with_raw_key::<NoCustomClaims>(&token, key_bytes, None).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<JWTError>(),
+        Some(JWTError::RegisteredCriticalHeader)
+    ));
+}
+
+#[test]
+fn crit_absent_header_value_is_rejected() {
+    let key_bytes = b"crit-test-key";
+    let token =
+        build_token_with_raw_header(r#"{"alg":"HS256","crit":["custom-ext"]}"#, key_bytes);
+    let options = VerificationOptions {
+        accepted_critical_headers: Some(HashSet::from_strings(&["custom-ext"])),
+        ..Default::default()
+    };
+    let err =
+        verify_with_raw_key::<NoCustomClaims>(&token, key_bytes, Some(options)).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<JWTError>(),
+        Some(JWTError::MissingCriticalHeaderValue)
+    ));
+}
+
+#[test]
+fn crit_without_accepted_set_is_rejected() {
+    let key_bytes = b"crit-test-key";
+    let token = build_token_with_raw_header(
+        r#"{"alg":"HS256","crit":["custom-ext"],"custom-ext":true}"#,
+        key_bytes,
+    );
+    let err = verify_with_raw_key::<NoCustomClaims>(&token, key_bytes, None).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<JWTError>(),
+        Some(JWTError::UnsupportedCriticalHeader)
+    ));
+}
+
+#[test]
+fn crit_not_in_accepted_set_is_rejected() {
+    let key_bytes = b"crit-test-key";
+    let token = build_token_with_raw_header(
+        r#"{"alg":"HS256","crit":["custom-ext"],"custom-ext":true}"#,
+        key_bytes,
+    );
+    let options = VerificationOptions {
+        accepted_critical_headers: Some(HashSet::from_strings(&["other-ext"])),
+        ..Default::default()
+    };
+    let err =
+        verify_with_raw_key::<NoCustomClaims>(&token, key_bytes, Some(options)).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<JWTError>(),
+        Some(JWTError::UnsupportedCriticalHeader)
+    ));
+}
+
+#[test]
+fn crit_in_accepted_set_is_allowed() {
+    let key_bytes = b"crit-test-key";
+    let token = build_token_with_raw_header(
+        r#"{"alg":"HS256","crit":["custom-ext"],"custom-ext":true}"#,
+        key_bytes,
+    );
+    let options = VerificationOptions {
+        accepted_critical_headers: Some(HashSet::from_strings(&["custom-ext"])),
+        ..Default::default()
+    };
+    verify_with_raw_key::<NoCustomClaims>(&token, key_bytes, Some(options)).unwrap();
+}
+
+#[test]
+fn oversized_token_is_rejected_before_parsing() {
+    use crate::prelude::*;
+
+    let key = HS256Key::generate();
+    let claims = Claims::create(Duration::from_mins(10));
+    let token = key.authenticate(claims).unwrap();
+    let oversized_token = format!("{}{}", token, "A".repeat(DEFAULT_MAX_TOKEN_LENGTH));
+
+    let err = key
+        .verify_token::<NoCustomClaims>(&oversized_token, None)
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<JWTError>(),
+        Some(JWTError::TokenTooLong)
+    ));
+}
+
+#[test]
+fn custom_max_token_length_is_honored() {
+    use crate::prelude::*;
+
+    let key = HS256Key::generate();
+    let claims = Claims::create(Duration::from_mins(10));
+    let token = key.authenticate(claims).unwrap();
+
+    let too_strict = VerificationOptions {
+        max_token_length: Some(token.len() - 1),
+        ..Default::default()
+    };
+    let err = key
+        .verify_token::<NoCustomClaims>(&token, Some(too_strict))
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<JWTError>(),
+        Some(JWTError::TokenTooLong)
+    ));
+
+    let exact_fit = VerificationOptions {
+        max_token_length: Some(token.len()),
+        ..Default::default()
+    };
+    key.verify_token::<NoCustomClaims>(&token, Some(exact_fit))
+        .unwrap();
+}